@@ -0,0 +1,112 @@
+use crate::{to_bmp, BMPColorType, BMPCompression, BMPConfig, ImageResource, ResizeFilter};
+
+/// The output format to use for [`to_auto`].
+#[derive(Debug, Clone, Copy)]
+pub enum AutoFormat {
+    /// Inspect the source image and pick a format automatically.
+    Auto,
+    /// Force a lossy JPEG. Not yet implemented by this crate; see [`to_auto`].
+    Jpeg,
+    /// Force a lossless PNG. Not yet implemented by this crate; see [`to_auto`].
+    Png,
+    /// Force a BMP.
+    Bmp,
+}
+
+impl Default for AutoFormat {
+    #[inline]
+    fn default() -> Self {
+        AutoFormat::Auto
+    }
+}
+
+/// The config used by [`to_auto`].
+#[derive(Debug, Default)]
+pub struct AutoConfig {
+    /// The width of the output image. `0` means the original width.
+    pub width: u16,
+    /// The height of the output image. `0` means the original height.
+    pub height: u16,
+    /// Only shrink the image, not to enlarge it.
+    pub shrink_only: bool,
+    /// The higher the sharper. The value is passed to the sharpen step as is; a negative value
+    /// is not adjusted automatically.
+    pub sharpen: f64,
+    /// The filter used when the image needs to be resized.
+    pub resize_filter: ResizeFilter,
+    /// The format to encode to, or `Auto` to let [`to_auto`] decide.
+    pub format: AutoFormat,
+}
+
+/// Above this unique-color count (relative to the pixel count), a source is treated as
+/// photographic rather than flat/graphic.
+const PHOTOGRAPHIC_COLOR_RATIO: f64 = 0.1;
+
+/// Classify the source image and delegate to the matching `to_*` encoder.
+///
+/// If the source has no alpha channel and looks photographic (a high ratio of unique colors
+/// to pixels), a lossy encoding is preferred; otherwise a lossless one is used instead, since
+/// flat/graphic images and images with transparency compress poorly and lose quality under
+/// lossy encoding. This crate currently only ships a BMP encoder, so `Auto` realizes that
+/// choice through `to_bmp`: a photographic source becomes a truecolor BMP, an alpha source
+/// becomes a 32-bit BMP, and a flat/graphic source becomes an indexed BMP.
+///
+/// `AutoFormat::Bmp` forces the indexed-BMP path. `AutoFormat::Jpeg` and `AutoFormat::Png`
+/// force a codec this crate does not implement yet, so they return an error rather than
+/// silently emitting a mislabeled BMP.
+pub fn to_auto(
+    output: &mut ImageResource,
+    input: &ImageResource,
+    config: &AutoConfig,
+) -> Result<(), &'static str> {
+    let color_type = match config.format {
+        AutoFormat::Jpeg => return Err("JPEG encoding is not yet supported by this crate."),
+        AutoFormat::Png => return Err("PNG encoding is not yet supported by this crate."),
+        AutoFormat::Bmp => BMPColorType::Indexed8 {
+            colors: 256,
+        },
+        AutoFormat::Auto => {
+            let bmp_config = BMPConfig {
+                width: config.width,
+                height: config.height,
+                shrink_only: config.shrink_only,
+                sharpen: config.sharpen,
+                background_color: None,
+                resize_filter: config.resize_filter,
+                color_type: BMPColorType::Rgb24,
+                compression: BMPCompression::None,
+            };
+
+            let (mw, _vector) = crate::fetch_magic_wand(input, &bmp_config)?;
+
+            let has_alpha = mw.get_image_alpha_channel();
+
+            let pixel_count = (mw.get_image_width() * mw.get_image_height()).max(1) as f64;
+            let unique_colors = mw.get_image_colors() as f64;
+            let photographic = !has_alpha && (unique_colors / pixel_count) > PHOTOGRAPHIC_COLOR_RATIO;
+
+            if photographic {
+                BMPColorType::Rgb24
+            } else if has_alpha {
+                BMPColorType::Rgba32
+            } else {
+                BMPColorType::Indexed8 {
+                    colors: 256,
+                }
+            }
+        }
+    };
+
+    let bmp_config = BMPConfig {
+        width: config.width,
+        height: config.height,
+        shrink_only: config.shrink_only,
+        sharpen: config.sharpen,
+        background_color: None,
+        resize_filter: config.resize_filter,
+        color_type,
+        compression: BMPCompression::None,
+    };
+
+    to_bmp(output, input, &bmp_config)
+}
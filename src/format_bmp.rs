@@ -1,8 +1,50 @@
 use crate::{
     compute_output_size_sharpen, fetch_magic_wand, magick_rust::{bindings, PixelWand},
     starts_ends_with_caseless::EndsWithCaseless, ColorName, ImageConfig, ImageResource, InterlaceType,
+    ResizeFilter,
 };
 
+#[derive(Debug, Clone, Copy)]
+/// The color type of the output BMP image.
+pub enum BMPColorType {
+    /// 24-bit truecolor, no alpha channel.
+    Rgb24,
+    /// 32-bit truecolor with an alpha channel.
+    Rgba32,
+    /// 8-bit grayscale.
+    Grayscale8,
+    /// 8-bit indexed color with a palette of up to `colors` entries.
+    Indexed8 {
+        colors: u16,
+    },
+}
+
+impl Default for BMPColorType {
+    #[inline]
+    fn default() -> Self {
+        BMPColorType::Rgb24
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The compression used by the output BMP image. RLE compression only applies to paletted
+/// (indexed) BMPs.
+pub enum BMPCompression {
+    /// No compression.
+    None,
+    /// 8-bit run-length encoding. Requires an `Indexed8` color type with up to 256 colors.
+    Rle8,
+    /// 4-bit run-length encoding. Requires an `Indexed8` color type with up to 16 colors.
+    Rle4,
+}
+
+impl Default for BMPCompression {
+    #[inline]
+    fn default() -> Self {
+        BMPCompression::None
+    }
+}
+
 #[derive(Debug)]
 /// The output config of a BMP image.
 pub struct BMPConfig {
@@ -16,6 +58,12 @@ pub struct BMPConfig {
     pub sharpen: f64,
     /// The color is used for fill up the alpha background.
     pub background_color: Option<ColorName>,
+    /// The filter used when the image needs to be resized.
+    pub resize_filter: ResizeFilter,
+    /// The color type (bit-depth/palette) of the output BMP image.
+    pub color_type: BMPColorType,
+    /// The compression used by the output BMP image.
+    pub compression: BMPCompression,
 }
 
 impl BMPConfig {
@@ -26,6 +74,10 @@ impl BMPConfig {
     ///     height: 0u16,
     ///     shrink_only: true,
     ///     sharpen: -1f64,
+    ///     background_color: None,
+    ///     resize_filter: ResizeFilter::Lanczos,
+    ///     color_type: BMPColorType::Rgb24,
+    ///     compression: BMPCompression::None,
     /// }
     /// ```
     #[inline]
@@ -36,6 +88,9 @@ impl BMPConfig {
             shrink_only: true,
             sharpen: -1f64,
             background_color: None,
+            resize_filter: ResizeFilter::Lanczos,
+            color_type: BMPColorType::Rgb24,
+            compression: BMPCompression::None,
         }
     }
 }
@@ -63,6 +118,10 @@ impl ImageConfig for BMPConfig {
     fn is_shrink_only(&self) -> bool {
         self.shrink_only
     }
+
+    fn get_resize_filter(&self) -> ResizeFilter {
+        self.resize_filter
+    }
 }
 
 /// Convert an image to a BMP image.
@@ -83,11 +142,72 @@ pub fn to_bmp(
     if !vector {
         let (width, height, sharpen) = compute_output_size_sharpen(&mw, config);
 
-        mw.resize_image(width as usize, height as usize, bindings::FilterType_LanczosFilter);
+        mw.resize_image(width as usize, height as usize, config.get_resize_filter().to_filter_type());
 
         mw.sharpen_image(0f64, sharpen)?;
     }
 
+    match config.color_type {
+        BMPColorType::Rgb24 => {
+            mw.set_image_alpha_channel(bindings::AlphaChannelOption_RemoveAlphaChannel)?;
+            mw.set_image_type(bindings::ImageType_TrueColorType)?;
+        }
+        BMPColorType::Rgba32 => {
+            mw.set_image_alpha_channel(bindings::AlphaChannelOption_ActivateAlphaChannel)?;
+            mw.set_image_type(bindings::ImageType_TrueColorAlphaType)?;
+        }
+        BMPColorType::Grayscale8 => {
+            mw.set_image_alpha_channel(bindings::AlphaChannelOption_RemoveAlphaChannel)?;
+            mw.set_image_type(bindings::ImageType_GrayscaleType)?;
+            mw.set_image_depth(8)?;
+        }
+        BMPColorType::Indexed8 {
+            colors,
+        } => {
+            if colors > 256 {
+                return Err("An 8-bit indexed BMP cannot hold a palette larger than 256 colors.");
+            }
+
+            mw.quantize_image(
+                colors as usize,
+                bindings::ColorspaceType_RGBColorspace,
+                0,
+                bindings::DitherMethod_NoDitherMethod,
+                0,
+            )?;
+            mw.set_image_type(bindings::ImageType_PaletteType)?;
+            mw.set_image_depth(8)?;
+        }
+    }
+
+    if config.compression != BMPCompression::None {
+        let max_colors = match config.compression {
+            BMPCompression::Rle8 => 256u16,
+            BMPCompression::Rle4 => 16u16,
+            BMPCompression::None => unreachable!(),
+        };
+
+        match config.color_type {
+            BMPColorType::Indexed8 {
+                colors,
+            } if colors <= max_colors => {
+                if config.compression == BMPCompression::Rle4 {
+                    mw.set_image_depth(4)?;
+                }
+
+                mw.set_image_compression(bindings::CompressionType_RLECompression)?;
+            }
+            BMPColorType::Indexed8 {
+                ..
+            } => {
+                return Err("The palette is too large for the requested RLE compression.");
+            }
+            _ => {
+                return Err("RLE compression can only be used with an indexed BMP color type.");
+            }
+        }
+    }
+
     mw.profile_image("*", None)?;
 
     mw.set_image_compression_quality(100)?;
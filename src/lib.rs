@@ -0,0 +1,167 @@
+extern crate magick_rust;
+extern crate starts_ends_with_caseless;
+
+mod format_auto;
+mod format_bmp;
+
+pub use format_auto::*;
+pub use format_bmp::*;
+
+use magick_rust::{bindings, MagickWand};
+
+/// The color names which can be used as a background color.
+#[derive(Debug, Clone, Copy)]
+pub enum ColorName {
+    Black,
+    White,
+    Red,
+    Green,
+    Blue,
+}
+
+impl ColorName {
+    #[inline]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ColorName::Black => "black",
+            ColorName::White => "white",
+            ColorName::Red => "red",
+            ColorName::Green => "green",
+            ColorName::Blue => "blue",
+        }
+    }
+}
+
+/// The interlace schemes which can be used for output images.
+#[derive(Debug, Clone, Copy)]
+pub enum InterlaceType {
+    NoInterlace,
+    LineInterlace,
+    PlaneInterlace,
+    PartitionInterlace,
+}
+
+impl InterlaceType {
+    #[inline]
+    pub fn ordinal(self) -> usize {
+        match self {
+            InterlaceType::NoInterlace => 0,
+            InterlaceType::LineInterlace => 1,
+            InterlaceType::PlaneInterlace => 2,
+            InterlaceType::PartitionInterlace => 3,
+        }
+    }
+}
+
+/// An input or output resource used by the `to_*` functions.
+pub enum ImageResource {
+    Path(String),
+    Data(Vec<u8>),
+    MagickWand(MagickWand),
+}
+
+/// The resize filter used when an image needs to be scaled down or up.
+///
+/// Each variant maps directly to one of the filters exposed by ImageMagick. Cheaper filters
+/// (e.g. `Nearest`) are a good fit for thumbnails, while more expensive ones (e.g. `Mitchell`)
+/// give better quality for photographic downscales.
+#[derive(Debug, Clone, Copy)]
+pub enum ResizeFilter {
+    Nearest,
+    Bilinear,
+    Bicubic,
+    Hermite,
+    Gaussian,
+    Bessel,
+    Sinc,
+    Lanczos,
+    Blackman,
+    Mitchell,
+}
+
+impl ResizeFilter {
+    #[inline]
+    pub fn to_filter_type(self) -> bindings::FilterType {
+        match self {
+            ResizeFilter::Nearest => bindings::FilterType_PointFilter,
+            ResizeFilter::Bilinear => bindings::FilterType_TriangleFilter,
+            ResizeFilter::Bicubic => bindings::FilterType_CatromFilter,
+            ResizeFilter::Hermite => bindings::FilterType_HermiteFilter,
+            ResizeFilter::Gaussian => bindings::FilterType_GaussianFilter,
+            ResizeFilter::Bessel => bindings::FilterType_JincFilter,
+            ResizeFilter::Sinc => bindings::FilterType_SincFilter,
+            ResizeFilter::Lanczos => bindings::FilterType_LanczosFilter,
+            ResizeFilter::Blackman => bindings::FilterType_BlackmanFilter,
+            ResizeFilter::Mitchell => bindings::FilterType_MitchellFilter,
+        }
+    }
+}
+
+impl Default for ResizeFilter {
+    #[inline]
+    fn default() -> Self {
+        ResizeFilter::Lanczos
+    }
+}
+
+/// Common config fields shared by every output format.
+pub trait ImageConfig {
+    fn get_width(&self) -> u16;
+
+    fn get_height(&self) -> u16;
+
+    fn get_sharpen(&self) -> f64;
+
+    fn is_shrink_only(&self) -> bool;
+
+    fn get_resize_filter(&self) -> ResizeFilter;
+}
+
+/// Load the input resource into a `MagickWand`, together with a vector-format flag.
+///
+/// Vector formats are not detected: the returned flag is always `false`.
+pub fn fetch_magic_wand(
+    input: &ImageResource,
+    _config: &dyn ImageConfig,
+) -> Result<(MagickWand, bool), &'static str> {
+    let mw = match input {
+        ImageResource::Path(p) => {
+            let mw = MagickWand::new();
+
+            mw.read_image(p.as_str()).map_err(|_| "The input image cannot be read.")?;
+
+            mw
+        }
+        ImageResource::Data(b) => {
+            let mw = MagickWand::new();
+
+            mw.read_image_blob(b).map_err(|_| "The input image cannot be read.")?;
+
+            mw
+        }
+        ImageResource::MagickWand(input_mw) => input_mw.clone(),
+    };
+
+    let vector = false;
+
+    Ok((mw, vector))
+}
+
+/// Compute the output width, height, and sharpen level for a resize operation.
+///
+/// The sharpen level is returned as configured; a negative value is not adjusted.
+pub fn compute_output_size_sharpen(mw: &MagickWand, config: &dyn ImageConfig) -> (u16, u16, f64) {
+    let original_width = mw.get_image_width() as u16;
+    let original_height = mw.get_image_height() as u16;
+    let sharpen = config.get_sharpen();
+
+    let mut width = if config.get_width() == 0 { original_width } else { config.get_width() };
+    let mut height = if config.get_height() == 0 { original_height } else { config.get_height() };
+
+    if config.is_shrink_only() {
+        width = width.min(original_width);
+        height = height.min(original_height);
+    }
+
+    (width, height, sharpen)
+}